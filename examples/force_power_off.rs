@@ -8,8 +8,8 @@ use std::io::Write;
 use std::process::exit;
 use waveshare_ups_hat_e::UpsHatE;
 
-fn confirm_power_off(args: &Vec<String>) -> bool {
-    if args.len() == 2 && args[1].to_ascii_lowercase() == "-y" {
+fn confirm_power_off(args: &[String]) -> bool {
+    if args.len() == 2 && args[1].eq_ignore_ascii_case("-y") {
         return true;
     }
 
@@ -18,13 +18,13 @@ fn confirm_power_off(args: &Vec<String>) -> bool {
 
     let mut input = String::new();
     std::io::stdin().read_line(&mut input).expect("failed to read input");
-    input.trim().to_ascii_lowercase() == "y"
+    input.trim().eq_ignore_ascii_case("y")
 }
 
 fn main() {
     let args = env::args().collect::<Vec<_>>();
 
-    if args.len() == 2 && args[1].to_ascii_lowercase() != "-y" {
+    if args.len() == 2 && !args[1].eq_ignore_ascii_case("-y") {
         println!("Usage: force_power_off [-y]");
         println!("  -y: skip confirmation prompt");
         println!();
@@ -3,6 +3,9 @@
 
 //! A "top" like monitor for the Waveshare UPS Hat E.
 //!
+//! Pass `--json` to emit one [`UpsSnapshot`](waveshare_ups_hat_e::UpsSnapshot) JSON line per
+//! interval instead of the ANSI dashboard, for piping into logging/metrics stacks. Requires the
+//! crate's `serde` feature.
 use std::io::{self, Write};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -15,9 +18,12 @@ const RESET: &str = "\x1b[0m";
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut ups = UpsHatE::new();
-    let mut stdout = io::stdout();
 
-    let software_revision = ups.get_software_revision()?;
+    if std::env::args().any(|arg| arg == "--json") {
+        return run_json(&mut ups);
+    }
+
+    let mut stdout = io::stdout();
 
     loop {
         let battery = ups.get_battery_state()?;
@@ -37,10 +43,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("Unix time: {epoch_secs}");
         println!();
 
-        println!("{BOLD}UPS Info{RESET}");
-        println!("  Software Rev:  {:?}", software_revision);
-        println!();
-
         // Power state
         println!("{BOLD}Power{RESET}");
         println!("  State:         {:?}", power.charging_state);
@@ -91,3 +93,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         thread::sleep(Duration::from_secs(2));
     }
 }
+
+#[cfg(feature = "serde")]
+fn run_json(ups: &mut UpsHatE) -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        let snapshot = ups.snapshot()?;
+        println!("{}", serde_json::to_string(&snapshot)?);
+        thread::sleep(Duration::from_secs(2));
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn run_json(_ups: &mut UpsHatE) -> Result<(), Box<dyn std::error::Error>> {
+    Err("--json requires the crate's `serde` feature; rebuild with `--features serde`".into())
+}
@@ -5,6 +5,8 @@
 
 pub mod error;
 pub mod registers;
+pub mod shutdown;
+pub mod watcher;
 
 use error::Error;
 use i2cdev::core::I2CDevice;
@@ -13,6 +15,8 @@ use registers::{
     BATTERY_REG, CELL_VOLTAGE_REG, CHARGING_REG, COMMUNICATION_REG, ChargerActivity, ChargingState,
     CommState, POWEROFF_REG, USBC_VBUS_REG, UsbCInputState, UsbCPowerDelivery,
 };
+use std::thread;
+use std::time::Duration;
 
 /// Default I2C address of the Waveshare UPS Hat E
 pub const DEFAULT_I2C_ADDRESS: u16 = 0x2d;
@@ -25,10 +29,51 @@ pub const DEFAULT_I2C_DEV_PATH: &str = "/dev/i2c-1";
 /// remaining to run a shutdown sequence.
 pub const DEFAULT_CELL_LOW_VOLTAGE_THRESHOLD: u16 = 3400; // 3.4V
 
+/// The default ceiling for a single cell's voltage, in millivolts, above which the cell is
+/// considered over-voltage. Typical Li-ion cells are not rated to charge past this point.
+pub const DEFAULT_CELL_OVER_VOLTAGE_THRESHOLD: u16 = 4250; // 4.25V
+
+/// The default floor for a single cell's voltage, in millivolts, below which the pack is
+/// considered dead rather than merely discharged, e.g. if it won't charge back up.
+pub const DEFAULT_CELL_DEAD_VOLTAGE_THRESHOLD: u16 = 2800; // 2.8V
+
+/// The default maximum allowed difference between the highest and lowest cell voltage, in
+/// millivolts, before the pack is considered imbalanced.
+pub const DEFAULT_CELL_IMBALANCE_THRESHOLD: u16 = 150;
+
 /// Value to write to the [`POWEROFF_REG`] register to initiate a power-off, or if read from
 /// [`POWEROFF_REG`], indicates that a power-off is pending.
 pub const POWEROFF_VALUE: u8 = 0x55;
 
+/// Recommended polling interval while the battery is actively charging or discharging, as
+/// returned by [`UpsHatE::recommended_poll_interval`].
+pub const FAST_POLL_INTERVAL: Duration = Duration::from_secs(40);
+
+/// Recommended polling interval while the UPS is idle/standby with the battery topped up or not
+/// yet needing attention, as returned by [`UpsHatE::recommended_poll_interval`].
+pub const SLOW_POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Retry policy for I2C reads/writes that fail transiently (EMI, bus contention).
+///
+/// Attempts back off exponentially: the `n`th retry waits `base_delay * 2^(n - 1)`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first. Must be at least 1.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles with each subsequent retry.
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 10 ms base delay (10 ms, then 20 ms between retries).
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(10),
+        }
+    }
+}
+
 /// Represents the composite power state of the UPS Hat E.
 #[derive(Debug)]
 pub struct PowerState {
@@ -65,6 +110,7 @@ pub struct BatteryState {
 
 /// Voltage readings for each of the four battery cells.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CellVoltage {
     pub cell_1_millivolts: u16,
     pub cell_2_millivolts: u16,
@@ -72,6 +118,11 @@ pub struct CellVoltage {
     pub cell_4_millivolts: u16,
 }
 
+/// Default smoothing factor for the `voltage_avg`/`current_avg` exponential moving average
+/// computed across successive [`UpsSnapshot`]s. Higher values track the instantaneous reading
+/// more closely; lower values smooth out more noise.
+pub const DEFAULT_EMA_ALPHA: f64 = 0.2;
+
 /// Voltage and current readings from the USB-C port.
 #[derive(Debug)]
 pub struct UsbCVBus {
@@ -80,6 +131,66 @@ pub struct UsbCVBus {
     pub milliwatts: u16,
 }
 
+/// Discrete battery health verdict, modeled on the Linux `power_supply` framework's `HEALTH`
+/// property. This is more actionable than a single "is it low" bool, since it distinguishes
+/// *why* the pack is in a bad state.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BatteryHealth {
+    /// Nothing notable detected.
+    Good,
+    /// A cell's voltage exceeds the over-voltage ceiling.
+    OverVoltage,
+    /// A cell's voltage is at/below the low-voltage cutoff.
+    UnderVoltage,
+    /// A cell's voltage is at/below the dead-pack floor while the charger is actively trying
+    /// (and failing) to bring it up.
+    Dead,
+    /// The BQ4050 safety timer expired before the pack finished charging.
+    SafetyTimerExpire,
+    /// The spread between the highest and lowest cell voltage exceeds the imbalance threshold.
+    CellImbalance,
+}
+
+/// Battery health verdict plus the readings that produced it, so callers can log why a
+/// particular [`BatteryHealth`] was reported.
+#[derive(Debug)]
+pub struct BatteryHealthReport {
+    pub health: BatteryHealth,
+    pub cell_voltages: CellVoltage,
+    pub charger_activity: ChargerActivity,
+}
+
+/// Charge/discharge status, mirroring the `power_supply` framework's `STATUS` property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    Full,
+    NotCharging,
+}
+
+/// A single-pass, point-in-time read of every register, aggregated into the `power_supply`-style
+/// fields a monitoring/metrics pipeline typically wants: `status`, `online`, `present`,
+/// `capacity`, `voltage_now`, `current_now`, plus a smoothed `voltage_avg`/`current_avg` since
+/// instantaneous readings are noisy.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UpsSnapshot {
+    pub status: BatteryStatus,
+    pub online: bool,
+    /// Whether the BQ4050 gas gauge is communicating normally. The UPS Hat E exposes no
+    /// dedicated battery-presence signal, so this is the closest available proxy.
+    pub present: bool,
+    pub capacity: u16,
+    pub voltage_now: u16,
+    pub current_now: i16,
+    pub voltage_avg: f64,
+    pub current_avg: f64,
+    pub cell_voltages: CellVoltage,
+    pub power_off_pending: bool,
+}
+
 /// Monitor a [Waveshare UPS HAT E](https://www.waveshare.com/wiki/UPS_HAT_(E))
 /// (Uninterruptible Power Supply model E) for a Raspberry Pi.
 ///
@@ -87,6 +198,13 @@ pub struct UsbCVBus {
 /// other interesting information
 pub struct UpsHatE {
     i2c_bus: LinuxI2CDevice,
+    cell_over_voltage_threshold: u16,
+    cell_dead_voltage_threshold: u16,
+    cell_imbalance_threshold: u16,
+    retry_policy: RetryPolicy,
+    ema_alpha: f64,
+    voltage_avg_millivolts: Option<f64>,
+    current_avg_milliamps: Option<f64>,
 }
 
 impl Default for UpsHatE {
@@ -96,7 +214,7 @@ impl Default for UpsHatE {
         let i2c = LinuxI2CDevice::new(DEFAULT_I2C_DEV_PATH, DEFAULT_I2C_ADDRESS)
             .expect("Failed to open I2C device");
 
-        Self { i2c_bus: i2c }
+        Self::from_i2c_device(i2c)
     }
 }
 
@@ -110,7 +228,55 @@ impl UpsHatE {
     /// Expert option: create a new instance of the UPS Hat E monitor using a custom I2C bus device
     /// (custom path and address).
     pub fn from_i2c_device(i2c_bus: LinuxI2CDevice) -> Self {
-        Self { i2c_bus }
+        Self {
+            i2c_bus,
+            cell_over_voltage_threshold: DEFAULT_CELL_OVER_VOLTAGE_THRESHOLD,
+            cell_dead_voltage_threshold: DEFAULT_CELL_DEAD_VOLTAGE_THRESHOLD,
+            cell_imbalance_threshold: DEFAULT_CELL_IMBALANCE_THRESHOLD,
+            retry_policy: RetryPolicy::default(),
+            ema_alpha: DEFAULT_EMA_ALPHA,
+            voltage_avg_millivolts: None,
+            current_avg_milliamps: None,
+        }
+    }
+
+    /// Set the retry policy used by [`read_block`](Self::read_block) and
+    /// [`force_power_off`](Self::force_power_off) when an I2C read/write fails transiently.
+    /// Defaults to [`RetryPolicy::default`].
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Set the smoothing factor used for the `voltage_avg`/`current_avg` fields of
+    /// [`snapshot`](Self::snapshot). Defaults to [`DEFAULT_EMA_ALPHA`].
+    pub fn set_ema_alpha(&mut self, alpha: f64) {
+        self.ema_alpha = alpha;
+    }
+
+    /// Set the per-cell voltage, in millivolts, above which [`get_battery_health`] reports
+    /// [`BatteryHealth::OverVoltage`]. Defaults to [`DEFAULT_CELL_OVER_VOLTAGE_THRESHOLD`].
+    ///
+    /// [`get_battery_health`]: UpsHatE::get_battery_health
+    pub fn set_cell_over_voltage_threshold(&mut self, millivolts: u16) {
+        self.cell_over_voltage_threshold = millivolts;
+    }
+
+    /// Set the per-cell voltage, in millivolts, at/below which [`get_battery_health`] reports
+    /// [`BatteryHealth::Dead`] while the charger is active. Defaults to
+    /// [`DEFAULT_CELL_DEAD_VOLTAGE_THRESHOLD`].
+    ///
+    /// [`get_battery_health`]: UpsHatE::get_battery_health
+    pub fn set_cell_dead_voltage_threshold(&mut self, millivolts: u16) {
+        self.cell_dead_voltage_threshold = millivolts;
+    }
+
+    /// Set the maximum allowed spread, in millivolts, between the highest and lowest cell
+    /// voltage before [`get_battery_health`] reports [`BatteryHealth::CellImbalance`]. Defaults
+    /// to [`DEFAULT_CELL_IMBALANCE_THRESHOLD`].
+    ///
+    /// [`get_battery_health`]: UpsHatE::get_battery_health
+    pub fn set_cell_imbalance_threshold(&mut self, millivolts: u16) {
+        self.cell_imbalance_threshold = millivolts;
     }
 
     pub fn get_cell_voltage(&mut self) -> Result<CellVoltage, Error> {
@@ -218,13 +384,106 @@ impl UpsHatE {
         Ok(total_voltage <= CUTOFF)
     }
 
+    /// Classify the overall health of the battery pack, modeled on the `power_supply` framework's
+    /// `HEALTH` property.
+    ///
+    /// Combines the four cell voltages from [`get_cell_voltage`](Self::get_cell_voltage) with the
+    /// charger activity from [`get_power_state`](Self::get_power_state): any cell over
+    /// [`DEFAULT_CELL_OVER_VOLTAGE_THRESHOLD`] is [`BatteryHealth::OverVoltage`], a
+    /// [`ChargerActivity::Timeout`] is [`BatteryHealth::SafetyTimerExpire`], a cell at/below the
+    /// dead-pack floor while the charger is actively driving current/voltage into it is
+    /// [`BatteryHealth::Dead`], a cell at/below [`DEFAULT_CELL_LOW_VOLTAGE_THRESHOLD`] is
+    /// [`BatteryHealth::UnderVoltage`], and a cell spread beyond the imbalance threshold is
+    /// [`BatteryHealth::CellImbalance`]. Checks are evaluated in that order and the first match
+    /// wins; otherwise the pack is [`BatteryHealth::Good`].
+    pub fn get_battery_health(&mut self) -> Result<BatteryHealthReport, Error> {
+        let cell_voltages = self.get_cell_voltage()?;
+        let charger_activity = self.get_power_state()?.charger_activity;
+
+        let health = classify_battery_health(
+            &cell_voltages,
+            charger_activity,
+            self.cell_over_voltage_threshold,
+            self.cell_dead_voltage_threshold,
+            self.cell_imbalance_threshold,
+        );
+
+        Ok(BatteryHealthReport {
+            health,
+            cell_voltages,
+            charger_activity,
+        })
+    }
+
+    /// Recommend a polling interval based on current charge activity, so a long-running daemon
+    /// can cut I2C traffic during steady-state operation without sacrificing responsiveness
+    /// during transitions.
+    ///
+    /// Returns [`FAST_POLL_INTERVAL`] whenever the battery is discharging (`milliamps < 0`,
+    /// checked first so a draining battery is always caught quickly), or whenever
+    /// [`ChargerActivity`] is anything other than [`ChargerActivity::Standby`] or
+    /// [`ChargerActivity::Full`]. Returns [`SLOW_POLL_INTERVAL`] otherwise, i.e. idle/standby or
+    /// topped up and plugged in.
+    pub fn recommended_poll_interval(&mut self) -> Result<Duration, Error> {
+        let milliamps = self.get_battery_state()?.milliamps;
+
+        if milliamps < 0 {
+            return Ok(FAST_POLL_INTERVAL);
+        }
+
+        let charger_activity = self.get_power_state()?.charger_activity;
+
+        Ok(recommended_interval(milliamps, charger_activity))
+    }
+
+    /// Read every register in one pass and aggregate the result into a `power_supply`-style
+    /// [`UpsSnapshot`], including a `voltage_avg`/`current_avg` exponential moving average across
+    /// successive calls (see [`set_ema_alpha`](Self::set_ema_alpha)).
+    pub fn snapshot(&mut self) -> Result<UpsSnapshot, Error> {
+        let battery = self.get_battery_state()?;
+        let power = self.get_power_state()?;
+        let comm = self.get_communication_state()?;
+        let cell_voltages = self.get_cell_voltage()?;
+        let power_off_pending = self.is_power_off_pending()?;
+
+        let status = battery_status(battery.milliamps, power.charger_activity, power.charging_state);
+
+        let ema_alpha = self.ema_alpha;
+        let voltage_avg = update_ema(
+            &mut self.voltage_avg_millivolts,
+            ema_alpha,
+            battery.millivolts as f64,
+        );
+        let current_avg = update_ema(
+            &mut self.current_avg_milliamps,
+            ema_alpha,
+            battery.milliamps as f64,
+        );
+
+        Ok(UpsSnapshot {
+            status,
+            online: matches!(power.usbc_input_state, UsbCInputState::Powered),
+            present: matches!(comm.bq4050, CommState::Normal),
+            capacity: battery.remaining_percent,
+            voltage_now: battery.millivolts,
+            current_now: battery.milliamps,
+            voltage_avg,
+            current_avg,
+            cell_voltages,
+            power_off_pending,
+        })
+    }
+
     /// Unconditionally and uncleanly power-off the Raspberry Pi in 30 seconds.
     ///
-    /// This operation cannot be canceled once called.
+    /// This operation cannot be canceled once called. Retries on transient I2C errors according
+    /// to the configured [`RetryPolicy`].
     pub fn force_power_off(&mut self) -> Result<(), Error> {
-        self.i2c_bus
-            .smbus_write_byte_data(POWEROFF_REG.id, POWEROFF_VALUE)?;
-        Ok(())
+        self.with_retries(|this| {
+            this.i2c_bus
+                .smbus_write_byte_data(POWEROFF_REG.id, POWEROFF_VALUE)
+                .map_err(Error::from)
+        })
     }
 
     /// Returns true if a power-off has been initiated.
@@ -234,13 +493,329 @@ impl UpsHatE {
         Ok(data[0] == POWEROFF_VALUE)
     }
 
+    /// Reads a block of registers, retrying on transient errors according to the configured
+    /// [`RetryPolicy`].
     fn read_block(&mut self, register: u8, length: u8) -> Result<Vec<u8>, Error> {
-        let data = self.i2c_bus.smbus_read_i2c_block_data(register, length)?;
+        self.with_retries(|this| {
+            let data = this.i2c_bus.smbus_read_i2c_block_data(register, length)?;
+
+            if data.len() != length as usize {
+                return Err(Error::InvalidDataLen(register, length as usize, data.len()));
+            }
+
+            Ok(data)
+        })
+    }
+
+    /// Run `op` against `self`, retrying on [`Error::I2CError`] and [`Error::InvalidDataLen`]
+    /// (a truncated block read is usually a transient glitch) up to `retry_policy.max_attempts`
+    /// times, backing off exponentially between attempts. Returns the last error if every
+    /// attempt fails.
+    fn with_retries<T>(
+        &mut self,
+        mut op: impl FnMut(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match op(self) {
+                Ok(value) => return Ok(value),
+                Err(err) if attempt < self.retry_policy.max_attempts && Self::is_retryable(&err) => {
+                    thread::sleep(backoff_delay(self.retry_policy.base_delay, attempt));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn is_retryable(err: &Error) -> bool {
+        matches!(err, Error::I2CError(_) | Error::InvalidDataLen(..))
+    }
+}
+
+/// Pure classification logic behind [`UpsHatE::get_battery_health`], pulled out of the method so
+/// it can be exercised without an I2C bus.
+///
+/// Checks are evaluated in this order and the first match wins; otherwise the pack is
+/// [`BatteryHealth::Good`]:
+/// 1. a cell over `over_voltage_threshold` -> [`BatteryHealth::OverVoltage`]
+/// 2. [`ChargerActivity::Timeout`] -> [`BatteryHealth::SafetyTimerExpire`]
+/// 3. a cell at/below `dead_voltage_threshold` while actively charging -> [`BatteryHealth::Dead`]
+/// 4. a cell at/below [`DEFAULT_CELL_LOW_VOLTAGE_THRESHOLD`] -> [`BatteryHealth::UnderVoltage`]
+/// 5. a cell spread at/above `imbalance_threshold` -> [`BatteryHealth::CellImbalance`]
+fn classify_battery_health(
+    cell_voltages: &CellVoltage,
+    charger_activity: ChargerActivity,
+    over_voltage_threshold: u16,
+    dead_voltage_threshold: u16,
+    imbalance_threshold: u16,
+) -> BatteryHealth {
+    let max_cell = cell_voltages
+        .cell_1_millivolts
+        .max(cell_voltages.cell_2_millivolts)
+        .max(cell_voltages.cell_3_millivolts)
+        .max(cell_voltages.cell_4_millivolts);
+    let min_cell = cell_voltages
+        .cell_1_millivolts
+        .min(cell_voltages.cell_2_millivolts)
+        .min(cell_voltages.cell_3_millivolts)
+        .min(cell_voltages.cell_4_millivolts);
+
+    if max_cell > over_voltage_threshold {
+        BatteryHealth::OverVoltage
+    } else if matches!(charger_activity, ChargerActivity::Timeout) {
+        BatteryHealth::SafetyTimerExpire
+    } else if min_cell <= dead_voltage_threshold
+        && matches!(
+            charger_activity,
+            ChargerActivity::ConstantCurrent | ChargerActivity::ConstantVoltage
+        )
+    {
+        BatteryHealth::Dead
+    } else if min_cell <= DEFAULT_CELL_LOW_VOLTAGE_THRESHOLD {
+        BatteryHealth::UnderVoltage
+    } else if max_cell - min_cell >= imbalance_threshold {
+        BatteryHealth::CellImbalance
+    } else {
+        BatteryHealth::Good
+    }
+}
+
+/// Delay before the `attempt`-th retry in [`UpsHatE::with_retries`]: `base_delay * 2^(attempt -
+/// 1)`, with the shift capped at 31 so a large `max_attempts` can't overflow `u32::pow`.
+fn backoff_delay(base_delay: Duration, attempt: u32) -> Duration {
+    let backoff_shift = (attempt - 1).min(31);
+    base_delay * 2u32.pow(backoff_shift)
+}
 
-        if data.len() != length as usize {
-            return Err(Error::InvalidDataLen(register, length as usize, data.len()));
+/// Decide the [`UpsHatE::recommended_poll_interval`] cadence for `milliamps`/`activity`.
+///
+/// [`FAST_POLL_INTERVAL`] whenever discharging (`milliamps < 0`) or the charger is doing
+/// anything other than idling or topped up; [`SLOW_POLL_INTERVAL`] otherwise.
+fn recommended_interval(milliamps: i16, activity: ChargerActivity) -> Duration {
+    if milliamps < 0 {
+        return FAST_POLL_INTERVAL;
+    }
+
+    match activity {
+        ChargerActivity::Standby | ChargerActivity::Full => SLOW_POLL_INTERVAL,
+        _ => FAST_POLL_INTERVAL,
+    }
+}
+
+/// Derive the `power_supply`-style [`BatteryStatus`] used by [`UpsHatE::snapshot`].
+fn battery_status(
+    milliamps: i16,
+    charger_activity: ChargerActivity,
+    charging_state: ChargingState,
+) -> BatteryStatus {
+    if matches!(charger_activity, ChargerActivity::Full) {
+        BatteryStatus::Full
+    } else if milliamps < 0 {
+        BatteryStatus::Discharging
+    } else if matches!(charging_state, ChargingState::Charging) {
+        BatteryStatus::Charging
+    } else {
+        BatteryStatus::NotCharging
+    }
+}
+
+/// Fold `sample` into the exponential moving average held in `prev`, seeding it with the first
+/// sample rather than starting from zero.
+fn update_ema(prev: &mut Option<f64>, alpha: f64, sample: f64) -> f64 {
+    let avg = match *prev {
+        Some(prev_avg) => alpha * sample + (1.0 - alpha) * prev_avg,
+        None => sample,
+    };
+
+    *prev = Some(avg);
+    avg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OVER: u16 = DEFAULT_CELL_OVER_VOLTAGE_THRESHOLD;
+    const DEAD: u16 = DEFAULT_CELL_DEAD_VOLTAGE_THRESHOLD;
+    const IMBALANCE: u16 = DEFAULT_CELL_IMBALANCE_THRESHOLD;
+
+    fn cells(mv: [u16; 4]) -> CellVoltage {
+        CellVoltage {
+            cell_1_millivolts: mv[0],
+            cell_2_millivolts: mv[1],
+            cell_3_millivolts: mv[2],
+            cell_4_millivolts: mv[3],
         }
+    }
+
+    #[test]
+    fn good_when_nothing_notable() {
+        let cell_voltages = cells([3800, 3800, 3800, 3800]);
+        let health = classify_battery_health(
+            &cell_voltages,
+            ChargerActivity::ConstantCurrent,
+            OVER,
+            DEAD,
+            IMBALANCE,
+        );
+        assert_eq!(health, BatteryHealth::Good);
+    }
+
+    #[test]
+    fn over_voltage_takes_priority_over_everything_else() {
+        let cell_voltages = cells([OVER + 1, 100, 100, 100]);
+        let health = classify_battery_health(
+            &cell_voltages,
+            ChargerActivity::Timeout,
+            OVER,
+            DEAD,
+            IMBALANCE,
+        );
+        assert_eq!(health, BatteryHealth::OverVoltage);
+    }
+
+    #[test]
+    fn safety_timer_expire_on_timeout() {
+        let cell_voltages = cells([3800, 3800, 3800, 3800]);
+        let health = classify_battery_health(
+            &cell_voltages,
+            ChargerActivity::Timeout,
+            OVER,
+            DEAD,
+            IMBALANCE,
+        );
+        assert_eq!(health, BatteryHealth::SafetyTimerExpire);
+    }
+
+    #[test]
+    fn dead_when_far_below_cutoff_while_actively_charging() {
+        let cell_voltages = cells([DEAD, 3800, 3800, 3800]);
+        let health = classify_battery_health(
+            &cell_voltages,
+            ChargerActivity::ConstantVoltage,
+            OVER,
+            DEAD,
+            IMBALANCE,
+        );
+        assert_eq!(health, BatteryHealth::Dead);
+    }
+
+    #[test]
+    fn under_voltage_when_low_but_not_actively_charging() {
+        let cell_voltages = cells([DEFAULT_CELL_LOW_VOLTAGE_THRESHOLD, 3800, 3800, 3800]);
+        let health = classify_battery_health(
+            &cell_voltages,
+            ChargerActivity::Standby,
+            OVER,
+            DEAD,
+            IMBALANCE,
+        );
+        assert_eq!(health, BatteryHealth::UnderVoltage);
+    }
+
+    #[test]
+    fn cell_imbalance_when_spread_too_wide() {
+        let cell_voltages = cells([3800, 3800 + IMBALANCE, 3800, 3800]);
+        let health = classify_battery_health(
+            &cell_voltages,
+            ChargerActivity::Standby,
+            OVER,
+            DEAD,
+            IMBALANCE,
+        );
+        assert_eq!(health, BatteryHealth::CellImbalance);
+    }
+
+    #[test]
+    fn backoff_delay_doubles_each_attempt() {
+        let base = Duration::from_millis(10);
+        assert_eq!(backoff_delay(base, 1), base);
+        assert_eq!(backoff_delay(base, 2), base * 2);
+        assert_eq!(backoff_delay(base, 3), base * 4);
+    }
+
+    #[test]
+    fn backoff_delay_shift_is_capped_to_avoid_pow_overflow() {
+        let base = Duration::from_millis(10);
+        // Without the cap, 2u32.pow(40) panics/overflows; attempts far past max_attempts
+        // shouldn't be reachable in practice, but the arithmetic must stay well-defined.
+        assert_eq!(backoff_delay(base, 40), base * 2u32.pow(31));
+    }
+
+    #[test]
+    fn poll_interval_is_fast_while_discharging() {
+        assert_eq!(
+            recommended_interval(-100, ChargerActivity::Full),
+            FAST_POLL_INTERVAL
+        );
+    }
+
+    #[test]
+    fn poll_interval_is_slow_when_idle_or_topped_up() {
+        assert_eq!(
+            recommended_interval(0, ChargerActivity::Standby),
+            SLOW_POLL_INTERVAL
+        );
+        assert_eq!(
+            recommended_interval(0, ChargerActivity::Full),
+            SLOW_POLL_INTERVAL
+        );
+    }
+
+    #[test]
+    fn poll_interval_is_fast_during_active_charging() {
+        assert_eq!(
+            recommended_interval(100, ChargerActivity::ConstantCurrent),
+            FAST_POLL_INTERVAL
+        );
+    }
+
+    #[test]
+    fn battery_status_full_takes_priority_over_milliamps() {
+        assert_eq!(
+            battery_status(-100, ChargerActivity::Full, ChargingState::NotCharging),
+            BatteryStatus::Full
+        );
+    }
+
+    #[test]
+    fn battery_status_discharging_when_milliamps_negative() {
+        assert_eq!(
+            battery_status(-100, ChargerActivity::ConstantCurrent, ChargingState::Charging),
+            BatteryStatus::Discharging
+        );
+    }
+
+    #[test]
+    fn battery_status_charging_when_charging_state_says_so() {
+        assert_eq!(
+            battery_status(100, ChargerActivity::ConstantCurrent, ChargingState::Charging),
+            BatteryStatus::Charging
+        );
+    }
+
+    #[test]
+    fn battery_status_not_charging_otherwise() {
+        assert_eq!(
+            battery_status(100, ChargerActivity::Standby, ChargingState::NotCharging),
+            BatteryStatus::NotCharging
+        );
+    }
+
+    #[test]
+    fn update_ema_seeds_with_first_sample() {
+        let mut avg = None;
+        assert_eq!(update_ema(&mut avg, 0.5, 10.0), 10.0);
+        assert_eq!(avg, Some(10.0));
+    }
 
-        Ok(data)
+    #[test]
+    fn update_ema_smooths_subsequent_samples() {
+        let mut avg = Some(10.0);
+        assert_eq!(update_ema(&mut avg, 0.5, 20.0), 15.0);
+        assert_eq!(avg, Some(15.0));
     }
 }
@@ -47,7 +47,7 @@ pub (crate) const POWEROFF_REG: RegisterBlock = RegisterBlock {
 };
 
 /// What kind of charging (if any) is taking place?
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChargerActivity {
     Standby = 0b000,
     Trickle = 0b001,
@@ -93,7 +93,7 @@ impl From<bool> for CommState {
 }
 
 /// Is USB-C power detected?
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UsbCInputState {
     NoPower = 0b0,
     Powered = 0b1,
@@ -110,7 +110,7 @@ impl From<bool> for UsbCInputState {
 }
 
 /// Was USB-C power delivery negotiated (`FastCharging`) or not (`StandardCharging`)?
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UsbCPowerDelivery {
     StandardCharging = 0b0,
     FastCharging = 0b1,
@@ -127,7 +127,7 @@ impl From<bool> for UsbCPowerDelivery {
 }
 
 /// Is the UPS charging or not?
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChargingState {
     NotCharging = 0b0,
     Charging = 0b1,
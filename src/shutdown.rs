@@ -0,0 +1,286 @@
+// Copyright (c) 2025 Stuart Stock
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Automatic graceful-shutdown supervisor, tying sustained low-battery detection to a clean
+//! power-down before the UPS cuts rail power.
+
+use crate::registers::UsbCInputState;
+use crate::UpsHatE;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Default interval at which a [`ShutdownGuard`] polls the battery state.
+pub const DEFAULT_GUARD_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default remaining-capacity floor, in percent, below which the battery is considered low.
+pub const DEFAULT_LOW_BATTERY_FLOOR_PERCENT: u16 = 10;
+
+/// Default number of consecutive low-battery polls required before a shutdown is triggered.
+pub const DEFAULT_DEBOUNCE_COUNT: u32 = 3;
+
+/// Default time given to the OS to shut down cleanly before [`UpsHatE::force_power_off`] is
+/// called as a hard fallback.
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Current phase of a [`ShutdownGuard`], so a UI can show e.g. "shutting down in T seconds".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardState {
+    /// Battery/power look fine; nothing in progress.
+    Monitoring,
+    /// The battery has looked low for `consecutive_low_polls` polls in a row, but the debounce
+    /// threshold hasn't been reached yet.
+    Debouncing { consecutive_low_polls: u32 },
+    /// The debounce threshold was reached: the shutdown action has been invoked and the guard is
+    /// waiting out the grace period before the hard cutoff.
+    ShuttingDown,
+    /// The grace period elapsed and [`UpsHatE::force_power_off`] was issued.
+    HardPoweroff,
+}
+
+struct Shared {
+    state: GuardState,
+    shutdown_deadline: Option<Instant>,
+}
+
+/// Watches for a sustained low-battery condition while unplugged, runs a user-supplied shutdown
+/// action, then falls back to [`UpsHatE::force_power_off`] after a grace period in case the OS
+/// hangs.
+pub struct ShutdownGuard {
+    ups: UpsHatE,
+    poll_interval: Duration,
+    low_battery_floor_percent: u16,
+    debounce_count: u32,
+    grace_period: Duration,
+    shutdown_action: Box<dyn FnMut() + Send>,
+}
+
+/// Handle to a [`ShutdownGuard`] running on a background thread.
+pub struct ShutdownGuardHandle {
+    shared: Arc<Mutex<Shared>>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownGuardHandle {
+    /// Current guard phase.
+    pub fn state(&self) -> GuardState {
+        self.shared.lock().unwrap().state
+    }
+
+    /// Time remaining until the hard `force_power_off` fallback fires, if a shutdown is
+    /// currently in progress.
+    pub fn time_until_poweroff(&self) -> Option<Duration> {
+        let shared = self.shared.lock().unwrap();
+        shared
+            .shutdown_deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Block until the guard thread exits, i.e. after it has issued the hard poweroff.
+    pub fn join(self) {
+        let _ = self.join_handle.join();
+    }
+}
+
+impl ShutdownGuard {
+    /// Create a guard over `ups` using the `DEFAULT_*` thresholds and the system
+    /// `shutdown`/`poweroff` command as the shutdown action.
+    pub fn new(ups: UpsHatE) -> Self {
+        Self {
+            ups,
+            poll_interval: DEFAULT_GUARD_POLL_INTERVAL,
+            low_battery_floor_percent: DEFAULT_LOW_BATTERY_FLOOR_PERCENT,
+            debounce_count: DEFAULT_DEBOUNCE_COUNT,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            shutdown_action: Box::new(default_shutdown_action),
+        }
+    }
+
+    /// Set how often the guard polls the battery/power state. Defaults to
+    /// [`DEFAULT_GUARD_POLL_INTERVAL`].
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Set the remaining-capacity floor, in percent, below which the battery is considered low.
+    /// Defaults to [`DEFAULT_LOW_BATTERY_FLOOR_PERCENT`].
+    pub fn with_low_battery_floor_percent(mut self, floor: u16) -> Self {
+        self.low_battery_floor_percent = floor;
+        self
+    }
+
+    /// Set the number of consecutive low-battery polls required before a shutdown is triggered.
+    /// Defaults to [`DEFAULT_DEBOUNCE_COUNT`].
+    pub fn with_debounce_count(mut self, count: u32) -> Self {
+        self.debounce_count = count;
+        self
+    }
+
+    /// Set how long the guard waits for a clean shutdown before calling
+    /// [`UpsHatE::force_power_off`] as a hard fallback. Defaults to [`DEFAULT_GRACE_PERIOD`].
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Replace the action invoked once the debounce threshold is reached. Defaults to invoking
+    /// the system `shutdown`/`poweroff` command.
+    pub fn with_shutdown_action(mut self, action: impl FnMut() + Send + 'static) -> Self {
+        self.shutdown_action = Box::new(action);
+        self
+    }
+
+    /// Spawn the guard's polling loop on a background thread.
+    ///
+    /// Once the battery has looked low (at/below `low_battery_floor_percent`, or
+    /// [`UpsHatE::is_battery_low`] by cell voltage — whichever trips first, since
+    /// `remaining_percent` can be miscalibrated) with no USB-C power for `debounce_count`
+    /// consecutive polls, the shutdown action is invoked exactly once and the guard enters
+    /// [`GuardState::ShuttingDown`]. If `grace_period` elapses without the thread having
+    /// otherwise exited, [`UpsHatE::force_power_off`] is called and the guard enters the
+    /// terminal [`GuardState::HardPoweroff`].
+    pub fn spawn(mut self) -> ShutdownGuardHandle {
+        let shared = Arc::new(Mutex::new(Shared {
+            state: GuardState::Monitoring,
+            shutdown_deadline: None,
+        }));
+        let shared_for_thread = Arc::clone(&shared);
+
+        let join_handle = thread::spawn(move || {
+            let mut consecutive_low_polls = 0u32;
+            let mut triggered = false;
+
+            loop {
+                thread::sleep(self.poll_interval);
+
+                if triggered {
+                    let deadline = shared_for_thread
+                        .lock()
+                        .unwrap()
+                        .shutdown_deadline
+                        .expect("shutdown_deadline set when triggered");
+
+                    if Instant::now() >= deadline {
+                        let _ = self.ups.force_power_off();
+                        shared_for_thread.lock().unwrap().state = GuardState::HardPoweroff;
+                        return;
+                    }
+
+                    continue;
+                }
+
+                let is_low_and_unpowered = match (self.ups.get_battery_state(), self.ups.get_power_state())
+                {
+                    (Ok(battery), Ok(power)) if power.usbc_input_state == UsbCInputState::NoPower => {
+                        battery.remaining_percent <= self.low_battery_floor_percent
+                            || self.ups.is_battery_low().unwrap_or(false)
+                    }
+                    _ => false,
+                };
+
+                let state;
+                (consecutive_low_polls, state) =
+                    debounce_step(consecutive_low_polls, is_low_and_unpowered, self.debounce_count);
+
+                if matches!(state, GuardState::ShuttingDown) {
+                    // Run the action before taking the lock so handle.state()/
+                    // time_until_poweroff() stay responsive even if it's slow.
+                    (self.shutdown_action)();
+
+                    let mut shared = shared_for_thread.lock().unwrap();
+                    shared.state = GuardState::ShuttingDown;
+                    shared.shutdown_deadline = Some(Instant::now() + self.grace_period);
+                    drop(shared);
+                    triggered = true;
+                    continue;
+                }
+
+                shared_for_thread.lock().unwrap().state = state;
+            }
+        });
+
+        ShutdownGuardHandle {
+            shared,
+            join_handle,
+        }
+    }
+}
+
+fn default_shutdown_action() {
+    if let Err(err) = Command::new("shutdown").args(["-h", "now"]).status() {
+        eprintln!("ShutdownGuard: failed to invoke shutdown command: {err}");
+    }
+}
+
+/// Advance the debounce counter by one poll and derive the [`GuardState`] it implies.
+///
+/// Any poll that isn't low-and-unpowered resets the counter to zero.
+fn debounce_step(
+    consecutive_low_polls: u32,
+    is_low_and_unpowered: bool,
+    debounce_count: u32,
+) -> (u32, GuardState) {
+    let consecutive_low_polls = if is_low_and_unpowered {
+        consecutive_low_polls + 1
+    } else {
+        0
+    };
+
+    let state = if consecutive_low_polls >= debounce_count {
+        GuardState::ShuttingDown
+    } else if consecutive_low_polls > 0 {
+        GuardState::Debouncing {
+            consecutive_low_polls,
+        }
+    } else {
+        GuardState::Monitoring
+    };
+
+    (consecutive_low_polls, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_monitoring_while_powered_or_above_floor() {
+        let (count, state) = debounce_step(0, false, 3);
+        assert_eq!(count, 0);
+        assert_eq!(state, GuardState::Monitoring);
+    }
+
+    #[test]
+    fn counts_up_while_low_and_unpowered() {
+        let (count, state) = debounce_step(0, true, 3);
+        assert_eq!(count, 1);
+        assert_eq!(state, GuardState::Debouncing { consecutive_low_polls: 1 });
+
+        let (count, state) = debounce_step(count, true, 3);
+        assert_eq!(count, 2);
+        assert_eq!(state, GuardState::Debouncing { consecutive_low_polls: 2 });
+    }
+
+    #[test]
+    fn resets_as_soon_as_power_or_capacity_recovers() {
+        let (count, state) = debounce_step(2, false, 3);
+        assert_eq!(count, 0);
+        assert_eq!(state, GuardState::Monitoring);
+    }
+
+    #[test]
+    fn reaching_debounce_count_triggers_shutdown() {
+        let (count, state) = debounce_step(2, true, 3);
+        assert_eq!(count, 3);
+        assert_eq!(state, GuardState::ShuttingDown);
+    }
+
+    #[test]
+    fn debounce_count_of_one_triggers_on_first_low_poll() {
+        let (count, state) = debounce_step(0, true, 1);
+        assert_eq!(count, 1);
+        assert_eq!(state, GuardState::ShuttingDown);
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright (c) 2025 Stuart Stock
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Background polling for [`UpsHatE`] that emits events only on meaningful state changes.
+
+use crate::error::Error;
+use crate::registers::{ChargerActivity, ChargingState, UsbCInputState, UsbCPowerDelivery};
+use crate::UpsHatE;
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// A meaningful state transition detected by a [`Watcher`], emitted only when something actually
+/// changed between polls rather than on every tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsEvent {
+    /// USB-C input power was plugged in or unplugged.
+    UsbCInputChanged(UsbCInputState),
+    /// USB-C power delivery (fast charge) negotiation changed.
+    UsbCPowerDeliveryChanged(UsbCPowerDelivery),
+    /// The battery finished charging.
+    ChargeCompleted,
+    /// The battery voltage crossed the low-voltage threshold; `true` means it just became low.
+    BatteryLowChanged(bool),
+    /// A power-off became pending.
+    PowerOffPending,
+}
+
+/// Snapshot of the fields a [`Watcher`] diffs between polls.
+struct WatcherSnapshot {
+    usbc_input_state: UsbCInputState,
+    usbc_power_delivery: UsbCPowerDelivery,
+    charger_activity: ChargerActivity,
+    charging_state: ChargingState,
+    battery_low: bool,
+    power_off_pending: bool,
+}
+
+/// Polls a [`UpsHatE`] on a background thread and emits [`UpsEvent`]s on an `mpsc` channel only
+/// when a meaningful transition is observed, rather than re-reading and reprinting every
+/// register on every tick.
+pub struct Watcher {
+    ups: UpsHatE,
+    interval: Duration,
+    adaptive: bool,
+}
+
+impl Watcher {
+    /// Create a watcher that polls `ups` every `interval` once [`spawn`](Self::spawn) is called.
+    pub fn new(ups: UpsHatE, interval: Duration) -> Self {
+        Self {
+            ups,
+            interval,
+            adaptive: false,
+        }
+    }
+
+    /// Opt into an adaptive polling cadence: after each poll, the next interval is chosen by
+    /// [`UpsHatE::recommended_poll_interval`] instead of staying fixed at the interval passed to
+    /// [`new`](Self::new), which is used only as the initial interval and as a fallback if a
+    /// recommendation read fails.
+    pub fn with_adaptive_polling(mut self) -> Self {
+        self.adaptive = true;
+        self
+    }
+
+    /// Take an initial snapshot, then spawn a background thread that polls every `interval` and
+    /// sends a [`UpsEvent`] on the returned channel for each meaningful transition.
+    ///
+    /// Returns an error if the initial snapshot read fails. Once running, a transient read error
+    /// on a later poll is treated as a missed tick: that poll is skipped and polling resumes on
+    /// the next interval. The background thread exits once the receiver is dropped.
+    pub fn spawn(mut self) -> Result<(Receiver<UpsEvent>, JoinHandle<()>), Error> {
+        let mut last = self.snapshot()?;
+        let (tx, rx) = mpsc::channel();
+        let default_interval = self.interval;
+        let adaptive = self.adaptive;
+        let mut sleep_interval = default_interval;
+
+        let handle = thread::spawn(move || {
+            loop {
+                thread::sleep(sleep_interval);
+
+                let current = match self.snapshot() {
+                    Ok(snapshot) => snapshot,
+                    Err(_) => continue,
+                };
+
+                for event in Self::diff(&last, &current) {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+
+                last = current;
+
+                if adaptive {
+                    sleep_interval = self
+                        .ups
+                        .recommended_poll_interval()
+                        .unwrap_or(default_interval);
+                }
+            }
+        });
+
+        Ok((rx, handle))
+    }
+
+    fn snapshot(&mut self) -> Result<WatcherSnapshot, Error> {
+        let power = self.ups.get_power_state()?;
+        let battery_low = self.ups.is_battery_low()?;
+        let power_off_pending = self.ups.is_power_off_pending()?;
+
+        Ok(WatcherSnapshot {
+            usbc_input_state: power.usbc_input_state,
+            usbc_power_delivery: power.usbc_power_delivery,
+            charger_activity: power.charger_activity,
+            charging_state: power.charging_state,
+            battery_low,
+            power_off_pending,
+        })
+    }
+
+    fn diff(prev: &WatcherSnapshot, current: &WatcherSnapshot) -> Vec<UpsEvent> {
+        let mut events = Vec::new();
+
+        if current.usbc_input_state != prev.usbc_input_state {
+            events.push(UpsEvent::UsbCInputChanged(current.usbc_input_state));
+        }
+
+        if current.usbc_power_delivery != prev.usbc_power_delivery {
+            events.push(UpsEvent::UsbCPowerDeliveryChanged(
+                current.usbc_power_delivery,
+            ));
+        }
+
+        let charge_completed = (current.charger_activity == ChargerActivity::Full
+            && prev.charger_activity != ChargerActivity::Full)
+            || (current.charging_state == ChargingState::NotCharging
+                && prev.charging_state == ChargingState::Charging
+                && current.usbc_input_state == UsbCInputState::Powered);
+        if charge_completed {
+            events.push(UpsEvent::ChargeCompleted);
+        }
+
+        if current.battery_low != prev.battery_low {
+            events.push(UpsEvent::BatteryLowChanged(current.battery_low));
+        }
+
+        if current.power_off_pending && !prev.power_off_pending {
+            events.push(UpsEvent::PowerOffPending);
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> WatcherSnapshot {
+        WatcherSnapshot {
+            usbc_input_state: UsbCInputState::Powered,
+            usbc_power_delivery: UsbCPowerDelivery::StandardCharging,
+            charger_activity: ChargerActivity::ConstantCurrent,
+            charging_state: ChargingState::Charging,
+            battery_low: false,
+            power_off_pending: false,
+        }
+    }
+
+    #[test]
+    fn no_events_when_nothing_changed() {
+        let prev = snapshot();
+        let current = snapshot();
+        assert!(Watcher::diff(&prev, &current).is_empty());
+    }
+
+    #[test]
+    fn usbc_input_change_is_reported() {
+        let prev = snapshot();
+        let current = WatcherSnapshot {
+            usbc_input_state: UsbCInputState::NoPower,
+            ..snapshot()
+        };
+        assert_eq!(
+            Watcher::diff(&prev, &current),
+            vec![UpsEvent::UsbCInputChanged(UsbCInputState::NoPower)]
+        );
+    }
+
+    #[test]
+    fn charge_completed_when_activity_becomes_full() {
+        let prev = snapshot();
+        let current = WatcherSnapshot {
+            charger_activity: ChargerActivity::Full,
+            ..snapshot()
+        };
+        assert_eq!(Watcher::diff(&prev, &current), vec![UpsEvent::ChargeCompleted]);
+    }
+
+    #[test]
+    fn charge_completed_when_charging_stops_while_powered() {
+        let prev = snapshot();
+        let current = WatcherSnapshot {
+            charging_state: ChargingState::NotCharging,
+            ..snapshot()
+        };
+        assert_eq!(Watcher::diff(&prev, &current), vec![UpsEvent::ChargeCompleted]);
+    }
+
+    #[test]
+    fn no_charge_completed_when_charging_stops_while_unpowered() {
+        let prev = WatcherSnapshot {
+            usbc_input_state: UsbCInputState::NoPower,
+            ..snapshot()
+        };
+        let current = WatcherSnapshot {
+            usbc_input_state: UsbCInputState::NoPower,
+            charging_state: ChargingState::NotCharging,
+            ..snapshot()
+        };
+        assert!(Watcher::diff(&prev, &current).is_empty());
+    }
+
+    #[test]
+    fn battery_low_change_is_reported() {
+        let prev = snapshot();
+        let current = WatcherSnapshot {
+            battery_low: true,
+            ..snapshot()
+        };
+        assert_eq!(
+            Watcher::diff(&prev, &current),
+            vec![UpsEvent::BatteryLowChanged(true)]
+        );
+    }
+
+    #[test]
+    fn power_off_pending_only_reported_on_rising_edge() {
+        let prev = WatcherSnapshot {
+            power_off_pending: true,
+            ..snapshot()
+        };
+        let current = WatcherSnapshot {
+            power_off_pending: true,
+            ..snapshot()
+        };
+        assert!(Watcher::diff(&prev, &current).is_empty());
+    }
+}